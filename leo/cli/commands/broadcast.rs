@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{helpers::broadcast_with_failover, *};
+use std::path::PathBuf;
+
+/// Broadcasts a previously-signed transaction, e.g. one produced by `leo deploy --save`.
+#[derive(Parser, Debug)]
+pub struct Broadcast {
+    #[clap(help = "Path to the JSON transaction file to broadcast.")]
+    pub(crate) transaction_file: PathBuf,
+    #[clap(
+        long = "endpoint",
+        help = "Endpoint(s) to broadcast the transaction to. May be given multiple times or as a comma-separated list; broadcasting tries each in order until one succeeds.",
+        default_value = "http://api.explorer.aleo.org/v1",
+        value_delimiter = ','
+    )]
+    pub(crate) endpoints: Vec<String>,
+    #[clap(long, help = "Network to broadcast the transaction to.", default_value = "testnet")]
+    pub(crate) network: String,
+}
+
+impl Command for Broadcast {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        // Read the saved transaction from disk.
+        let raw = std::fs::read_to_string(&self.transaction_file)
+            .map_err(|err| CliError::failed_to_read_transaction_file(&self.transaction_file, err))?;
+        let transaction: Transaction<CurrentNetwork> = serde_json::from_str(&raw)?;
+
+        println!("📦 Loaded transaction from '{}'...\n", self.transaction_file.display());
+
+        // Broadcast the transaction, trying each configured endpoint in order until one accepts it. This
+        // matters most here: a saved transaction can't be re-signed if broadcast fails, so there's no
+        // fallback short of re-running `deploy --save` from the signing machine.
+        broadcast_with_failover(&self.endpoints, &self.network, transaction, &self.transaction_file.display().to_string())?;
+
+        Ok(())
+    }
+}
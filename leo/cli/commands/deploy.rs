@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use super::*;
+use super::{helpers::broadcast_with_failover, *};
 use aleo_std::StorageMode;
 use snarkvm::{
     cli::helpers::dotenv_private_key,
@@ -28,13 +28,23 @@ use snarkvm::{
         VM,
     },
 };
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 /// Deploys an Aleo program.
 #[derive(Parser, Debug)]
 pub struct Deploy {
-    #[clap(long, help = "Endpoint to retrieve network state from.", default_value = "http://api.explorer.aleo.org/v1")]
-    pub endpoint: String,
+    #[clap(
+        long = "endpoint",
+        help = "Endpoint(s) to broadcast to. Network state is retrieved from the first one. May be given multiple times or as a comma-separated list; broadcasting tries each in order until one succeeds.",
+        default_value = "http://api.explorer.aleo.org/v1",
+        value_delimiter = ','
+    )]
+    pub endpoints: Vec<String>,
     #[clap(flatten)]
     pub(crate) fee_options: FeeOptions,
     #[clap(long, help = "Disables building of the project before deployment.", default_value = "false")]
@@ -43,10 +53,29 @@ pub struct Deploy {
     pub(crate) recursive: bool,
     #[clap(
         long,
-        help = "Time in seconds to wait between consecutive deployments. This is to help prevent a program from trying to be included in an earlier block than its dependency program.",
+        help = "Time in seconds to poll between confirmation checks of consecutive deployments. This is to help prevent a program from trying to be included in an earlier block than its dependency program.",
         default_value = "12"
     )]
     pub(crate) wait: u64,
+    #[clap(
+        long,
+        help = "Time in seconds to wait for a deployment to be confirmed on-chain before giving up on a dependency.",
+        default_value = "300"
+    )]
+    pub(crate) confirmation_timeout: u64,
+    #[clap(
+        long,
+        help = "Performs a dry run: prints the estimated deployment cost for each program without authorizing a fee or broadcasting.",
+        default_value = "false"
+    )]
+    pub(crate) dry_run: bool,
+    #[clap(long, help = "Aborts the deployment if the total cost (base cost + priority fee) exceeds this amount, in microcredits.")]
+    pub(crate) max_fee: Option<u64>,
+    #[clap(
+        long,
+        help = "Writes each signed deployment transaction as JSON to `<program name>.json` in this directory (created if missing) instead of broadcasting them. Use the `broadcast` command to submit the saved file(s) later."
+    )]
+    pub(crate) save: Option<PathBuf>,
 }
 
 impl Command for Deploy {
@@ -68,16 +97,21 @@ impl Command for Deploy {
         // Get the program name.
         let project_name = context.open_manifest()?.program_id().to_string();
 
-        // Get the private key.
-        let private_key = match &self.fee_options.private_key {
-            Some(key) => PrivateKey::from_str(key)?,
-            None => PrivateKey::from_str(
-                &dotenv_private_key().map_err(CliError::failed_to_read_environment_private_key)?.to_string(),
-            )?,
+        // Get the private key, unless this is a dry run: dry-run only needs `deployment_cost`, so it
+        // shouldn't require a key to be configured.
+        let private_key = if self.dry_run {
+            None
+        } else {
+            Some(match &self.fee_options.private_key {
+                Some(key) => PrivateKey::from_str(key)?,
+                None => PrivateKey::from_str(
+                    &dotenv_private_key().map_err(CliError::failed_to_read_environment_private_key)?.to_string(),
+                )?,
+            })
         };
 
-        // Specify the query
-        let query = SnarkVMQuery::from(&self.endpoint);
+        // Specify the query, using the primary (first) endpoint to retrieve network state.
+        let query = SnarkVMQuery::from(&self.endpoints[0]);
 
         let mut all_paths: Vec<(String, PathBuf)> = Vec::new();
 
@@ -103,7 +137,39 @@ impl Command for Deploy {
             let deployment = package.deploy::<CurrentAleo>(None)?;
             let deployment_id = deployment.to_deployment_id()?;
 
-            // Generate the deployment transaction.
+            // Compute the minimum deployment cost.
+            let (minimum_deployment_cost, _) = deployment_cost(&deployment)?;
+            let total_cost = minimum_deployment_cost.saturating_add(self.fee_options.priority_fee);
+
+            // If this is a dry run, print the estimated cost and move on without authorizing a fee or
+            // broadcasting. A dry run is non-destructive, so it always shows its numbers even if
+            // `--max-fee` would otherwise abort the deployment.
+            if self.dry_run {
+                println!("📝 Dry run for '{}':", name.bold());
+                println!("  Deployment ID: {deployment_id}");
+                println!("  Program size: {} bytes", deployment.to_bytes_le()?.len());
+                println!("  Minimum deployment cost: {minimum_deployment_cost} microcredits");
+                println!("  Priority fee: {} microcredits", self.fee_options.priority_fee);
+                println!("  Total cost: {total_cost} microcredits");
+                if let Some(max_fee) = self.max_fee {
+                    if total_cost > max_fee {
+                        println!("  ⚠️  Total cost exceeds --max-fee of {max_fee} microcredits");
+                    }
+                }
+                println!();
+                continue;
+            }
+
+            // If a maximum fee was specified, abort before authorizing anything that would exceed it.
+            if let Some(max_fee) = self.max_fee {
+                if total_cost > max_fee {
+                    return Err(CliError::deployment_cost_exceeds_max_fee(name, total_cost, max_fee).into());
+                }
+            }
+
+            // Generate the deployment transaction. `private_key` is guaranteed to be present here, since
+            // a dry run would have already `continue`d above.
+            let private_key = private_key.as_ref().expect("private key is required unless --dry-run");
             let transaction = {
                 // Initialize an RNG.
                 let rng = &mut rand::thread_rng();
@@ -114,15 +180,12 @@ impl Command for Deploy {
                 // Initialize the VM.
                 let vm = VM::from(store)?;
 
-                // Compute the minimum deployment cost.
-                let (minimum_deployment_cost, _) = deployment_cost(&deployment)?;
-
                 // Prepare the fees.
                 let fee = match &self.fee_options.record {
                     Some(record) => {
-                        let fee_record = parse_record(&private_key, record)?;
+                        let fee_record = parse_record(private_key, record)?;
                         let fee_authorization = vm.authorize_fee_private(
-                            &private_key,
+                            private_key,
                             fee_record,
                             minimum_deployment_cost,
                             self.fee_options.priority_fee,
@@ -133,7 +196,7 @@ impl Command for Deploy {
                     }
                     None => {
                         let fee_authorization = vm.authorize_fee_public(
-                            &private_key,
+                            private_key,
                             minimum_deployment_cost,
                             self.fee_options.priority_fee,
                             deployment_id,
@@ -143,25 +206,73 @@ impl Command for Deploy {
                     }
                 };
                 // Construct the owner.
-                let owner = ProgramOwner::new(&private_key, deployment_id, rng)?;
+                let owner = ProgramOwner::new(private_key, deployment_id, rng)?;
 
                 // Create a new transaction.
                 Transaction::from_deployment(owner, deployment, fee)?
             };
             println!("✅ Created deployment transaction for '{}'", name.bold());
 
+            // If `--save` was specified, write the signed transaction to disk instead of broadcasting it.
+            // This allows the transaction to be signed on an air-gapped machine and broadcast elsewhere
+            // with the `broadcast` command.
+            if let Some(save_dir) = &self.save {
+                std::fs::create_dir_all(save_dir)?;
+                let transaction_path = save_dir.join(format!("{name}.json"));
+                std::fs::write(&transaction_path, serde_json::to_string_pretty(&transaction)?)
+                    .map_err(|err| CliError::failed_to_save_transaction(&transaction_path, err))?;
+                println!("💾 Saved deployment transaction for '{}' to '{}'\n", name.bold(), transaction_path.display());
+                // Nothing was broadcast, so there's no on-chain state to wait for before the next program.
+                continue;
+            }
+
             // Determine if the transaction should be broadcast, stored, or displayed to the user.
-            handle_broadcast(
-                &format!("{}/{}/transaction/broadcast", self.endpoint, self.fee_options.network),
-                transaction,
-                name,
-            )?;
+            // Tries each configured endpoint in order until one accepts the transaction.
+            let transaction_id = transaction.id();
+            let broadcast_endpoint = broadcast_with_failover(&self.endpoints, &self.fee_options.network, transaction, name)?;
 
+            // Before deploying the next program, poll until this one is confirmed on-chain, rather than
+            // hoping a fixed sleep was long enough. This makes recursive deploys deterministic. Poll the
+            // endpoint that actually accepted the broadcast, not necessarily the first configured one.
             if index < all_paths.len() - 1 {
-                std::thread::sleep(std::time::Duration::from_secs(self.wait));
+                wait_for_confirmation(
+                    broadcast_endpoint,
+                    &self.fee_options.network,
+                    transaction_id,
+                    Duration::from_secs(self.wait),
+                    Duration::from_secs(self.confirmation_timeout),
+                )?;
             }
         }
 
         Ok(())
     }
 }
+
+/// Polls `{endpoint}/{network}/transaction/{transaction_id}` on an interval of `poll_interval` until the
+/// transaction is confirmed, returning a `CliError` if it is not confirmed within `timeout`.
+fn wait_for_confirmation(
+    endpoint: &str,
+    network: &str,
+    transaction_id: impl Display,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<()> {
+    let url = format!("{endpoint}/{network}/transaction/{transaction_id}");
+    let deadline = Instant::now() + timeout;
+
+    println!("⏳ Waiting for confirmation of transaction '{transaction_id}'...");
+
+    loop {
+        if ureq::get(&url).call().is_ok() {
+            println!("✅ Transaction '{transaction_id}' confirmed.\n");
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            return Err(CliError::deployment_confirmation_timeout(transaction_id, timeout.as_secs()).into());
+        }
+
+        std::thread::sleep(poll_interval);
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// Attempts to broadcast `transaction` to each of `endpoints` in order, returning as soon as one succeeds.
+/// This guards against a single rate-limited or unavailable node blocking an otherwise-valid transaction,
+/// whether it's a live deployment from `deploy` or a previously-signed one submitted via `broadcast`.
+/// Returns the endpoint that accepted the transaction, so callers can poll it for confirmation.
+pub(crate) fn broadcast_with_failover<'a>(
+    endpoints: &'a [String],
+    network: &str,
+    transaction: Transaction<CurrentNetwork>,
+    name: &str,
+) -> Result<&'a str> {
+    for endpoint in endpoints {
+        let url = format!("{endpoint}/{network}/transaction/broadcast");
+        match handle_broadcast(&url, transaction.clone(), name) {
+            Ok(()) => {
+                println!("📡 Broadcast of '{}' succeeded via '{endpoint}'\n", name.bold());
+                return Ok(endpoint);
+            }
+            Err(err) => println!("⚠️  Broadcast of '{}' via '{endpoint}' failed: {err}", name.bold()),
+        }
+    }
+    Err(CliError::all_broadcast_endpoints_failed(name).into())
+}